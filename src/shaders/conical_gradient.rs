@@ -0,0 +1,370 @@
+// Copyright 2006 The Android Open Source Project
+// Copyright 2020 Evgeniy Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use crate::{Color, Point, Transform};
+
+use super::gradient::{self, Gradient, GradientError, GradientStop, Interpolation, Ramp};
+use super::{SpreadMode, StageRec};
+
+/// A two-point conical gradient shader.
+///
+/// Unlike [`RadialGradient`](super::RadialGradient), which samples circles that share a
+/// single center and grow from zero, a conical gradient interpolates between an arbitrary
+/// start circle `(c0, r0)` and end circle `(c1, r1)`. This is the general form used by SVG's
+/// `<radialGradient>` with a focal point and by CSS radial gradients.
+#[derive(Clone, Debug)]
+pub struct ConicalGradient {
+    pub(crate) base: Gradient,
+    pub(crate) c0: Point,
+    pub(crate) r0: f32,
+    pub(crate) c1: Point,
+    pub(crate) r1: f32,
+}
+
+impl ConicalGradient {
+    /// Creates a new conical gradient.
+    ///
+    /// Returns an error when either point or radius is not finite, when a radius is
+    /// negative, or when `stops` is malformed. A single stop is expanded into a `[0, 1]` ramp
+    /// of that one color rather than being rejected.
+    ///
+    /// `c0 == c1 && r0 == r1` is accepted: the circle family collapses to a single circle and
+    /// no sample solves for a gradient parameter, so the gradient renders fully transparent
+    /// rather than being rejected at construction time.
+    pub fn new(
+        c0: Point,
+        r0: f32,
+        c1: Point,
+        r1: f32,
+        stops: Vec<GradientStop>,
+        mode: SpreadMode,
+        transform: Transform,
+    ) -> Result<Self, GradientError> {
+        gradient::validate_point(c0)?;
+        gradient::validate_point(c1)?;
+        gradient::validate_radius(r0)?;
+        gradient::validate_radius(r1)?;
+
+        let stops = gradient::expand_single_stop(stops);
+        gradient::validate_stops(&stops)?;
+
+        Ok(ConicalGradient {
+            base: Gradient {
+                stops,
+                mode,
+                transform,
+                interpolation: Interpolation::default(),
+            },
+            c0,
+            r0,
+            c1,
+            r1,
+        })
+    }
+
+    /// Returns the start circle's center.
+    #[inline]
+    pub fn c0(&self) -> Point {
+        self.c0
+    }
+
+    /// Returns the start circle's radius.
+    #[inline]
+    pub fn r0(&self) -> f32 {
+        self.r0
+    }
+
+    /// Returns the end circle's center.
+    #[inline]
+    pub fn c1(&self) -> Point {
+        self.c1
+    }
+
+    /// Returns the end circle's radius.
+    #[inline]
+    pub fn r1(&self) -> f32 {
+        self.r1
+    }
+
+    /// Returns the gradient's color stops.
+    #[inline]
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.base.stops
+    }
+
+    /// Returns the gradient's spread mode.
+    #[inline]
+    pub fn mode(&self) -> SpreadMode {
+        self.base.mode
+    }
+
+    /// A general two-point cone can have points that solve no root in its family (`solve_t`
+    /// returns `None`, rendering transparent), so it is reported as not opaque regardless of
+    /// its stops. The one exception is a concentric gradient (`c0 == c1`) with `r0 != r1`: every
+    /// point's distance from the shared center solves a valid, non-negative radius, so every
+    /// pixel is covered and opacity reduces to the stops, like `RadialGradient`.
+    #[inline]
+    pub(crate) fn is_opaque(&self) -> bool {
+        self.c0 == self.c1 && self.r0 != self.r1 && self.base.is_opaque()
+    }
+
+    pub(crate) fn push_stages(&self, rec: StageRec) -> bool {
+        let inv = match self.base.transform.invert() {
+            Some(inv) => inv,
+            None => return false,
+        };
+
+        let ctx = Ctx {
+            ramp: self.base.ramp(),
+            c0: self.c0,
+            r0: self.r0,
+            dc: Point::from_xy(self.c1.x - self.c0.x, self.c1.y - self.c0.y),
+            dr: self.r1 - self.r0,
+        };
+
+        rec.pipeline.push_transform(inv);
+        let handle = rec.ctx_storage.push(ctx);
+        rec.pipeline.push_conical_gradient(handle);
+
+        true
+    }
+}
+
+/// The precomputed per-pixel state for a conical gradient, handed to the raster pipeline.
+///
+/// Concentric circles (`c0 == c1`) are *not* special-cased: setting `dc = (0, 0)` makes
+/// `solve_t`'s quadratic degenerate to the correct single root `t = (dist(p, c0) - r0) / dr`
+/// on its own, so every radius offset (including `r0 != 0`) is handled without a separate
+/// code path.
+struct Ctx {
+    ramp: Ramp,
+    c0: Point,
+    r0: f32,
+    dc: Point,
+    dr: f32,
+}
+
+impl Ctx {
+    /// Solves for the gradient parameter `t` at sample point `p`, or `None` when `p` falls
+    /// outside every circle in the family (transparent, per the two-point conical contract).
+    fn solve_t(&self, p: Point) -> Option<f32> {
+        let px = p.x - self.c0.x;
+        let py = p.y - self.c0.y;
+
+        let a = self.dc.x * self.dc.x + self.dc.y * self.dc.y - self.dr * self.dr;
+        let b = px * self.dc.x + py * self.dc.y + self.r0 * self.dr;
+        let c = px * px + py * py - self.r0 * self.r0;
+
+        let t = if a.abs() < 1e-6 {
+            // `|dc| == |dr|`: the focal point (where the radius reaches zero) lies on the start
+            // circle, so the quadratic term vanishes and the equation has a single root. This
+            // is distinct from `r0 == r1`, the equal-radius "strip" (which keeps `c0 != c1` and
+            // so keeps `a = dc . dc != 0`, going through the quadratic branch below like any
+            // other cone).
+            if b.abs() < 1e-6 {
+                return None;
+            }
+            c / (2.0 * b)
+        } else {
+            let discriminant = b * b - a * c;
+            if discriminant < 0.0 {
+                return None;
+            }
+            let sqrt_d = discriminant.sqrt();
+
+            let t0 = (b + sqrt_d) / a;
+            let t1 = (b - sqrt_d) / a;
+            let (hi, lo) = if t0 > t1 { (t0, t1) } else { (t1, t0) };
+
+            if self.radius_at(hi) >= 0.0 {
+                hi
+            } else if self.radius_at(lo) >= 0.0 {
+                lo
+            } else {
+                return None;
+            }
+        };
+
+        if self.radius_at(t) < 0.0 {
+            return None;
+        }
+
+        Some(t)
+    }
+
+    fn radius_at(&self, t: f32) -> f32 {
+        self.r0 + t * self.dr
+    }
+
+    fn eval(&self, p: Point) -> Color {
+        match self.solve_t(p) {
+            Some(t) => self.ramp.color_at(t),
+            None => Color::TRANSPARENT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn stop(position: f32) -> GradientStop {
+        GradientStop::new(position, Color::BLACK)
+    }
+
+    fn ctx(c0: Point, r0: f32, c1: Point, r1: f32) -> Ctx {
+        Ctx {
+            ramp: Gradient {
+                stops: vec![stop(0.0), stop(1.0)],
+                mode: SpreadMode::Pad,
+                transform: Transform::identity(),
+                interpolation: Interpolation::default(),
+            }
+            .ramp(),
+            c0,
+            r0,
+            dc: Point::from_xy(c1.x - c0.x, c1.y - c0.y),
+            dr: r1 - r0,
+        }
+    }
+
+    // See linear_gradient.rs's test module for why generic validation isn't re-tested here;
+    // the tests below exercise this shape's own geometry (`solve_t`/`eval`) instead.
+    #[test]
+    fn allows_same_center_different_radii() {
+        // `c0 == c1` with `r0 != r1` is a valid concentric gradient, not a degenerate case.
+        let gradient = ConicalGradient::new(
+            Point::from_xy(0.0, 0.0),
+            0.0,
+            Point::from_xy(0.0, 0.0),
+            1.0,
+            vec![stop(0.0), stop(1.0)],
+            SpreadMode::Pad,
+            Transform::identity(),
+        )
+        .unwrap();
+
+        assert_eq!(gradient.c0(), gradient.c1());
+    }
+
+    #[test]
+    fn fully_degenerate_circles_construct_and_render_transparent() {
+        // `c0 == c1 && r0 == r1`: every sample falls outside the single collapsed circle.
+        let gradient = ConicalGradient::new(
+            Point::from_xy(0.0, 0.0),
+            1.0,
+            Point::from_xy(0.0, 0.0),
+            1.0,
+            vec![stop(0.0), stop(1.0)],
+            SpreadMode::Pad,
+            Transform::identity(),
+        )
+        .unwrap();
+
+        let c = ctx(gradient.c0(), gradient.r0(), gradient.c1(), gradient.r1());
+        assert_eq!(c.solve_t(Point::from_xy(0.0, 0.0)), None);
+        assert_eq!(c.eval(Point::from_xy(5.0, 5.0)), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn solves_t_on_the_start_and_end_circles() {
+        // A growing cone from (0, 0, r=0) to (0, 0, r=2): `dist == r0 + t * dr`.
+        let c = ctx(
+            Point::from_xy(0.0, 0.0),
+            0.0,
+            Point::from_xy(0.0, 0.0),
+            2.0,
+        );
+
+        assert_eq!(c.solve_t(Point::from_xy(0.0, 0.0)), Some(0.0));
+        assert!((c.solve_t(Point::from_xy(2.0, 0.0)).unwrap() - 1.0).abs() < 1e-5);
+        assert!((c.solve_t(Point::from_xy(1.0, 0.0)).unwrap() - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn concentric_gradient_honors_a_nonzero_inner_radius() {
+        // r0 = 0.5, r1 = 1.0: t should be the offset from r0, not from 0.
+        let c = ctx(
+            Point::from_xy(0.0, 0.0),
+            0.5,
+            Point::from_xy(0.0, 0.0),
+            1.0,
+        );
+
+        assert!((c.solve_t(Point::from_xy(0.5, 0.0)).unwrap() - 0.0).abs() < 1e-5);
+        assert!((c.solve_t(Point::from_xy(1.0, 0.0)).unwrap() - 1.0).abs() < 1e-5);
+        assert!((c.solve_t(Point::from_xy(0.75, 0.0)).unwrap() - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn concentric_gradient_reverses_with_shrinking_radius() {
+        // r0 = 1.0, r1 = 0.5: `t` should still run 0..1 from the r0 circle to the r1 circle.
+        let c = ctx(
+            Point::from_xy(0.0, 0.0),
+            1.0,
+            Point::from_xy(0.0, 0.0),
+            0.5,
+        );
+
+        assert!((c.solve_t(Point::from_xy(1.0, 0.0)).unwrap() - 0.0).abs() < 1e-5);
+        assert!((c.solve_t(Point::from_xy(0.5, 0.0)).unwrap() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn returns_none_outside_every_circle_in_the_family() {
+        // An equal-radius cone (a strip along the line through both centers) never covers
+        // points further than `r0` from that line, so far-away samples have no valid root.
+        let c = ctx(
+            Point::from_xy(0.0, 0.0),
+            1.0,
+            Point::from_xy(10.0, 0.0),
+            1.0,
+        );
+
+        assert_eq!(c.solve_t(Point::from_xy(-100.0, 100.0)), None);
+        assert_eq!(c.eval(Point::from_xy(-100.0, 100.0)), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn is_opaque_only_for_the_fully_covering_concentric_case() {
+        let concentric = ConicalGradient::new(
+            Point::from_xy(0.0, 0.0),
+            0.0,
+            Point::from_xy(0.0, 0.0),
+            1.0,
+            vec![stop(0.0), stop(1.0)],
+            SpreadMode::Pad,
+            Transform::identity(),
+        )
+        .unwrap();
+        assert!(concentric.is_opaque());
+
+        let general_cone = ConicalGradient::new(
+            Point::from_xy(0.0, 0.0),
+            1.0,
+            Point::from_xy(10.0, 0.0),
+            1.0,
+            vec![stop(0.0), stop(1.0)],
+            SpreadMode::Pad,
+            Transform::identity(),
+        )
+        .unwrap();
+        assert!(!general_cone.is_opaque());
+
+        let fully_degenerate = ConicalGradient::new(
+            Point::from_xy(0.0, 0.0),
+            1.0,
+            Point::from_xy(0.0, 0.0),
+            1.0,
+            vec![stop(0.0), stop(1.0)],
+            SpreadMode::Pad,
+            Transform::identity(),
+        )
+        .unwrap();
+        assert!(!fully_degenerate.is_opaque());
+    }
+}
@@ -0,0 +1,424 @@
+// Copyright 2006 The Android Open Source Project
+// Copyright 2020 Evgeniy Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use crate::{Color, Point, Transform};
+
+use super::SpreadMode;
+
+/// A stop in a gradient.
+///
+/// Each stop binds a color to a position along the gradient's `0..1` axis.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GradientStop {
+    position: f32,
+    color: Color,
+}
+
+impl GradientStop {
+    /// Creates a new stop.
+    ///
+    /// `position` is not validated here; gradient constructors reject stops whose positions
+    /// are outside `0..=1` or non-monotonic instead of silently fixing them up.
+    #[inline]
+    pub fn new(position: f32, color: Color) -> Self {
+        GradientStop { position, color }
+    }
+
+    /// Returns the stop's position.
+    #[inline]
+    pub fn position(&self) -> f32 {
+        self.position
+    }
+
+    /// Returns the stop's color.
+    #[inline]
+    pub fn color(&self) -> Color {
+        self.color
+    }
+}
+
+/// Selects how a gradient interpolates between two color stops.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Interpolation {
+    /// Interpolate each stop's straight (non-premultiplied) color.
+    ///
+    /// This is what SVG and CSS gradients do by default.
+    Straight,
+
+    /// Interpolate each stop's premultiplied color, then un-premultiply the result.
+    ///
+    /// This avoids dark halos where a transparent stop meets an opaque one, at the cost of
+    /// diverging from the SVG default. Skia and Blink offer the same choice when constructing
+    /// gradient shaders.
+    Premultiplied,
+}
+
+impl Default for Interpolation {
+    #[inline]
+    fn default() -> Self {
+        Interpolation::Straight
+    }
+}
+
+/// Describes a shader's gradient configuration, if it has one.
+///
+/// Returned by [`Shader::as_gradient`](super::Shader::as_gradient), this mirrors Skia's
+/// `asAGradient` query: it lets downstream consumers (SVG/PDF exporters, serializers, test
+/// harnesses) read a shader's stops, geometry, and spread mode back out without owning the
+/// original builder inputs.
+#[derive(Copy, Clone, Debug)]
+pub enum GradientInfo<'a> {
+    /// A solid color shader.
+    Color(Color),
+    /// A linear gradient, from `start` to `end`.
+    Linear {
+        /// The gradient's start point.
+        start: Point,
+        /// The gradient's end point.
+        end: Point,
+        /// The gradient's color stops.
+        stops: &'a [GradientStop],
+        /// The gradient's spread mode.
+        mode: SpreadMode,
+    },
+    /// A radial gradient, centered at `center` with `radius`.
+    Radial {
+        /// The gradient's center point.
+        center: Point,
+        /// The gradient's radius.
+        radius: f32,
+        /// The gradient's color stops.
+        stops: &'a [GradientStop],
+        /// The gradient's spread mode.
+        mode: SpreadMode,
+    },
+    /// A two-point conical gradient between the start circle `(c0, r0)` and the end circle
+    /// `(c1, r1)`.
+    Conical {
+        /// The start circle's center.
+        c0: Point,
+        /// The start circle's radius.
+        r0: f32,
+        /// The end circle's center.
+        c1: Point,
+        /// The end circle's radius.
+        r1: f32,
+        /// The gradient's color stops.
+        stops: &'a [GradientStop],
+        /// The gradient's spread mode.
+        mode: SpreadMode,
+    },
+    /// A sweep gradient, centered at `center` and starting at `start_angle` radians.
+    Sweep {
+        /// The gradient's center point.
+        center: Point,
+        /// The angle, in radians, at which the first stop is placed.
+        start_angle: f32,
+        /// The gradient's color stops.
+        stops: &'a [GradientStop],
+        /// The gradient's spread mode.
+        mode: SpreadMode,
+    },
+}
+
+/// Why a gradient's constructor inputs were rejected.
+///
+/// Mirrors the guard Skia's `valid_grad` performs before building a gradient shader: malformed
+/// input is reported instead of silently producing garbage or panicking.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GradientError {
+    /// No stops were provided.
+    NoStops,
+    /// A stop's position lies outside `0..=1`, or is not finite.
+    InvalidStopPosition,
+    /// Stop positions are not monotonically increasing.
+    StopPositionsNotMonotonic,
+    /// A point or radius contains a non-finite (`NaN` or infinite) value.
+    NonFinite,
+    /// A radius is negative.
+    NegativeRadius,
+    /// The gradient's geometry is degenerate (a linear gradient whose start and end points
+    /// coincide, or a radial gradient whose radius is zero).
+    DegenerateGeometry,
+}
+
+impl core::fmt::Display for GradientError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let msg = match self {
+            GradientError::NoStops => "gradient has no color stops",
+            GradientError::InvalidStopPosition => "a gradient stop's position is outside 0..=1",
+            GradientError::StopPositionsNotMonotonic => {
+                "gradient stop positions are not monotonically increasing"
+            }
+            GradientError::NonFinite => "a gradient point or radius is not finite",
+            GradientError::NegativeRadius => "a gradient radius is negative",
+            GradientError::DegenerateGeometry => "gradient geometry is degenerate",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// Validates that `point`'s coordinates are finite.
+pub(crate) fn validate_point(point: Point) -> Result<(), GradientError> {
+    if point.x.is_finite() && point.y.is_finite() {
+        Ok(())
+    } else {
+        Err(GradientError::NonFinite)
+    }
+}
+
+/// Validates that `radius` is finite and non-negative.
+pub(crate) fn validate_radius(radius: f32) -> Result<(), GradientError> {
+    if !radius.is_finite() {
+        Err(GradientError::NonFinite)
+    } else if radius < 0.0 {
+        Err(GradientError::NegativeRadius)
+    } else {
+        Ok(())
+    }
+}
+
+/// Expands a single stop into a two-stop `[0, 1]` ramp, duplicating its color.
+///
+/// This mirrors Skia's handling of one-color gradients: rather than being rejected, they
+/// degrade into a valid solid fill.
+pub(crate) fn expand_single_stop(stops: Vec<GradientStop>) -> Vec<GradientStop> {
+    if stops.len() == 1 {
+        let color = stops[0].color();
+        vec![GradientStop::new(0.0, color), GradientStop::new(1.0, color)]
+    } else {
+        stops
+    }
+}
+
+/// Validates that `stops` is non-empty, every position is finite and within `0..=1`, and
+/// positions are monotonically increasing.
+pub(crate) fn validate_stops(stops: &[GradientStop]) -> Result<(), GradientError> {
+    if stops.is_empty() {
+        return Err(GradientError::NoStops);
+    }
+
+    let mut prev = f32::NEG_INFINITY;
+    for stop in stops {
+        let position = stop.position();
+        if !(0.0..=1.0).contains(&position) {
+            return Err(GradientError::InvalidStopPosition);
+        }
+
+        if position < prev {
+            return Err(GradientError::StopPositionsNotMonotonic);
+        }
+
+        prev = position;
+    }
+
+    Ok(())
+}
+
+/// The state shared by every gradient kind.
+#[derive(Clone, Debug)]
+pub(crate) struct Gradient {
+    pub(crate) stops: Vec<GradientStop>,
+    pub(crate) mode: SpreadMode,
+    pub(crate) transform: Transform,
+    pub(crate) interpolation: Interpolation,
+}
+
+impl Gradient {
+    /// A gradient is opaque only when every one of its stops is opaque, regardless of the
+    /// interpolation mode: premultiplied interpolation only changes *how* colors blend between
+    /// stops, not whether the stops themselves carry alpha.
+    pub(crate) fn is_opaque(&self) -> bool {
+        self.stops.iter().all(|stop| stop.color.is_opaque())
+    }
+
+    /// Precomputes the ramp used to evaluate this gradient's color at a given `t`.
+    ///
+    /// For [`Interpolation::Premultiplied`] this converts every stop into premultiplied space
+    /// once, up front, so the per-pixel pipeline stage only has to lerp and un-premultiply.
+    pub(crate) fn ramp(&self) -> Ramp {
+        let stops = match self.interpolation {
+            Interpolation::Straight => self.stops.clone(),
+            Interpolation::Premultiplied => self
+                .stops
+                .iter()
+                .map(|stop| GradientStop::new(stop.position(), premultiply(stop.color())))
+                .collect(),
+        };
+
+        Ramp {
+            stops,
+            mode: self.mode,
+            interpolation: self.interpolation,
+        }
+    }
+}
+
+/// A gradient's stops, precomputed for per-pixel evaluation.
+#[derive(Clone, Debug)]
+pub(crate) struct Ramp {
+    stops: Vec<GradientStop>,
+    mode: SpreadMode,
+    interpolation: Interpolation,
+}
+
+impl Ramp {
+    /// Resolves `t` through the spread mode and returns the interpolated color.
+    pub(crate) fn color_at(&self, t: f32) -> Color {
+        let t = resolve_t(t, self.mode);
+        let c = color_at_t(&self.stops, t);
+        match self.interpolation {
+            Interpolation::Straight => c,
+            Interpolation::Premultiplied => unpremultiply(c),
+        }
+    }
+}
+
+fn premultiply(c: Color) -> Color {
+    let a = c.alpha();
+    Color::from_rgba(c.red() * a, c.green() * a, c.blue() * a, a).unwrap_or(c)
+}
+
+fn unpremultiply(c: Color) -> Color {
+    let a = c.alpha();
+    if a <= 0.0 {
+        return c;
+    }
+
+    Color::from_rgba(
+        (c.red() / a).min(1.0),
+        (c.green() / a).min(1.0),
+        (c.blue() / a).min(1.0),
+        a,
+    )
+    .unwrap_or(c)
+}
+
+/// Maps a raw gradient parameter `t` into a `0..1` range according to `mode`.
+pub(crate) fn resolve_t(t: f32, mode: SpreadMode) -> f32 {
+    match mode {
+        SpreadMode::Pad => t.max(0.0).min(1.0),
+        SpreadMode::Repeat => t.rem_euclid(1.0),
+        SpreadMode::Reflect => {
+            let t = t.rem_euclid(2.0);
+            if t > 1.0 {
+                2.0 - t
+            } else {
+                t
+            }
+        }
+    }
+}
+
+/// Interpolates the color at a parametric position `t` (already resolved into `0..1`)
+/// across a sorted stop list.
+pub(crate) fn color_at_t(stops: &[GradientStop], t: f32) -> Color {
+    debug_assert!(!stops.is_empty());
+
+    if t <= stops[0].position {
+        return stops[0].color;
+    }
+
+    let last = stops.len() - 1;
+    if t >= stops[last].position {
+        return stops[last].color;
+    }
+
+    for pair in stops.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if t >= a.position && t <= b.position {
+            let span = b.position - a.position;
+            let f = if span > 0.0 { (t - a.position) / span } else { 0.0 };
+            return lerp_color(a.color, b.color, f);
+        }
+    }
+
+    stops[last].color
+}
+
+fn lerp_color(c0: Color, c1: Color, f: f32) -> Color {
+    Color::from_rgba(
+        c0.red() + (c1.red() - c0.red()) * f,
+        c0.green() + (c1.green() - c0.green()) * f,
+        c0.blue() + (c1.blue() - c0.blue()) * f,
+        c0.alpha() + (c1.alpha() - c0.alpha()) * f,
+    )
+    .unwrap_or(c0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stop(position: f32) -> GradientStop {
+        GradientStop::new(position, Color::BLACK)
+    }
+
+    #[test]
+    fn validate_point_rejects_non_finite_coordinates() {
+        assert_eq!(
+            validate_point(Point::from_xy(f32::NAN, 0.0)),
+            Err(GradientError::NonFinite)
+        );
+        assert_eq!(
+            validate_point(Point::from_xy(0.0, f32::INFINITY)),
+            Err(GradientError::NonFinite)
+        );
+        assert_eq!(validate_point(Point::from_xy(1.0, 2.0)), Ok(()));
+    }
+
+    #[test]
+    fn validate_radius_rejects_non_finite_and_negative_values() {
+        assert_eq!(validate_radius(f32::NAN), Err(GradientError::NonFinite));
+        assert_eq!(
+            validate_radius(f32::INFINITY),
+            Err(GradientError::NonFinite)
+        );
+        assert_eq!(validate_radius(-1.0), Err(GradientError::NegativeRadius));
+        assert_eq!(validate_radius(0.0), Ok(()));
+        assert_eq!(validate_radius(1.0), Ok(()));
+    }
+
+    #[test]
+    fn validate_stops_rejects_empty_stops() {
+        assert_eq!(validate_stops(&[]), Err(GradientError::NoStops));
+    }
+
+    #[test]
+    fn validate_stops_rejects_out_of_range_position() {
+        assert_eq!(
+            validate_stops(&[stop(-0.1), stop(1.0)]),
+            Err(GradientError::InvalidStopPosition)
+        );
+    }
+
+    #[test]
+    fn validate_stops_rejects_non_monotonic_positions() {
+        assert_eq!(
+            validate_stops(&[stop(0.5), stop(0.2)]),
+            Err(GradientError::StopPositionsNotMonotonic)
+        );
+    }
+
+    #[test]
+    fn validate_stops_accepts_sorted_in_range_positions() {
+        assert_eq!(validate_stops(&[stop(0.0), stop(0.5), stop(1.0)]), Ok(()));
+    }
+
+    #[test]
+    fn expand_single_stop_duplicates_the_lone_color_across_a_full_ramp() {
+        let stops = expand_single_stop(vec![stop(0.5)]);
+        assert_eq!(stops.len(), 2);
+        assert_eq!(stops[0].position(), 0.0);
+        assert_eq!(stops[1].position(), 1.0);
+    }
+
+    #[test]
+    fn expand_single_stop_leaves_multiple_stops_untouched() {
+        let stops = expand_single_stop(vec![stop(0.0), stop(0.5), stop(1.0)]);
+        assert_eq!(stops.len(), 3);
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2006 The Android Open Source Project
+// Copyright 2020 Evgeniy Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use crate::{Color, Point, Transform};
+
+use super::gradient::{self, Gradient, GradientError, GradientStop, Interpolation, Ramp};
+use super::{SpreadMode, StageRec};
+
+/// A linear gradient shader.
+#[derive(Clone, Debug)]
+pub struct LinearGradient {
+    pub(crate) base: Gradient,
+    pub(crate) start: Point,
+    pub(crate) end: Point,
+}
+
+impl LinearGradient {
+    /// Creates a new linear gradient.
+    ///
+    /// Returns an error when `start` or `end` is not finite, when `start` and `end` are the
+    /// same point, or when `stops` is malformed. A single stop is expanded into a `[0, 1]`
+    /// ramp of that one color rather than being rejected.
+    pub fn new(
+        start: Point,
+        end: Point,
+        stops: Vec<GradientStop>,
+        mode: SpreadMode,
+        transform: Transform,
+        interpolation: Interpolation,
+    ) -> Result<Self, GradientError> {
+        gradient::validate_point(start)?;
+        gradient::validate_point(end)?;
+
+        if start == end {
+            return Err(GradientError::DegenerateGeometry);
+        }
+
+        let stops = gradient::expand_single_stop(stops);
+        gradient::validate_stops(&stops)?;
+
+        Ok(LinearGradient {
+            base: Gradient {
+                stops,
+                mode,
+                transform,
+                interpolation,
+            },
+            start,
+            end,
+        })
+    }
+
+    /// Returns the gradient's start point.
+    #[inline]
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    /// Returns the gradient's end point.
+    #[inline]
+    pub fn end(&self) -> Point {
+        self.end
+    }
+
+    /// Returns the gradient's color stops.
+    #[inline]
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.base.stops
+    }
+
+    /// Returns the gradient's spread mode.
+    #[inline]
+    pub fn mode(&self) -> SpreadMode {
+        self.base.mode
+    }
+
+    #[inline]
+    pub(crate) fn is_opaque(&self) -> bool {
+        self.base.is_opaque()
+    }
+
+    pub(crate) fn push_stages(&self, rec: StageRec) -> bool {
+        let inv = match self.base.transform.invert() {
+            Some(inv) => inv,
+            None => return false,
+        };
+
+        let ctx = Ctx {
+            ramp: self.base.ramp(),
+            start: self.start,
+            end: self.end,
+        };
+
+        rec.pipeline.push_transform(inv);
+        let handle = rec.ctx_storage.push(ctx);
+        rec.pipeline.push_linear_gradient(handle);
+
+        true
+    }
+}
+
+/// The precomputed per-pixel state for a linear gradient, handed to the raster pipeline.
+struct Ctx {
+    ramp: Ramp,
+    start: Point,
+    end: Point,
+}
+
+impl Ctx {
+    fn eval(&self, p: Point) -> Color {
+        let dx = self.end.x - self.start.x;
+        let dy = self.end.y - self.start.y;
+        let len2 = dx * dx + dy * dy;
+
+        let t = if len2 > 0.0 {
+            ((p.x - self.start.x) * dx + (p.y - self.start.y) * dy) / len2
+        } else {
+            0.0
+        };
+
+        self.ramp.color_at(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn stop(position: f32) -> GradientStop {
+        GradientStop::new(position, Color::BLACK)
+    }
+
+    // Generic stop/point/radius validation and single-stop expansion are exercised once,
+    // directly against the shared `gradient::` helpers, in `gradient.rs`'s own test module.
+    // Only this shape's own geometry check lives here.
+    #[test]
+    fn rejects_coincident_points() {
+        let err = LinearGradient::new(
+            Point::from_xy(1.0, 1.0),
+            Point::from_xy(1.0, 1.0),
+            vec![stop(0.0), stop(1.0)],
+            SpreadMode::Pad,
+            Transform::identity(),
+            Interpolation::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err, GradientError::DegenerateGeometry);
+    }
+}
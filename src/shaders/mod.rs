@@ -7,11 +7,15 @@
 mod gradient;
 mod linear_gradient;
 mod radial_gradient;
+mod conical_gradient;
+mod sweep_gradient;
 mod pattern;
 
-pub use gradient::GradientStop;
+pub use gradient::{GradientError, GradientInfo, GradientStop, Interpolation};
 pub use linear_gradient::LinearGradient;
 pub use radial_gradient::RadialGradient;
+pub use conical_gradient::ConicalGradient;
+pub use sweep_gradient::SweepGradient;
 pub use pattern::{Pattern, FilterQuality};
 
 use crate::{Color, Transform};
@@ -57,6 +61,10 @@ pub enum Shader<'a> {
     LinearGradient(LinearGradient),
     /// A radial gradient shader.
     RadialGradient(RadialGradient),
+    /// A two-point conical gradient shader.
+    ConicalGradient(ConicalGradient),
+    /// A sweep (angular/conic) gradient shader.
+    SweepGradient(SweepGradient),
     /// A pattern shader.
     Pattern(Pattern<'a>),
 }
@@ -68,11 +76,51 @@ impl<'a> Shader<'a> {
         match self {
             Shader::SolidColor(ref c) => c.is_opaque(),
             Shader::LinearGradient(ref g) => g.is_opaque(),
-            Shader::RadialGradient(_) => false,
+            Shader::RadialGradient(ref g) => g.is_opaque(),
+            Shader::ConicalGradient(ref g) => g.is_opaque(),
+            Shader::SweepGradient(ref g) => g.is_opaque(),
             Shader::Pattern(_) => false,
         }
     }
 
+    /// Returns the shader's gradient configuration, if it has one.
+    ///
+    /// Modeled on Skia's `asAGradient`, this lets downstream consumers (SVG/PDF exporters,
+    /// serializers, test harnesses) round-trip a shader's stops, geometry, and spread mode
+    /// without owning the original builder inputs.
+    pub fn as_gradient(&self) -> Option<GradientInfo<'_>> {
+        match self {
+            Shader::SolidColor(c) => Some(GradientInfo::Color(*c)),
+            Shader::LinearGradient(g) => Some(GradientInfo::Linear {
+                start: g.start(),
+                end: g.end(),
+                stops: g.stops(),
+                mode: g.mode(),
+            }),
+            Shader::RadialGradient(g) => Some(GradientInfo::Radial {
+                center: g.center(),
+                radius: g.radius(),
+                stops: g.stops(),
+                mode: g.mode(),
+            }),
+            Shader::ConicalGradient(g) => Some(GradientInfo::Conical {
+                c0: g.c0(),
+                r0: g.r0(),
+                c1: g.c1(),
+                r1: g.r1(),
+                stops: g.stops(),
+                mode: g.mode(),
+            }),
+            Shader::SweepGradient(g) => Some(GradientInfo::Sweep {
+                center: g.center(),
+                start_angle: g.start_angle(),
+                stops: g.stops(),
+                mode: g.mode(),
+            }),
+            Shader::Pattern(_) => None,
+        }
+    }
+
     // Unlike Skia, we do not have is_constant, because we don't have Color shaders.
 
     /// If this returns false, then we draw nothing (do not fall back to shader context)
@@ -81,6 +129,8 @@ impl<'a> Shader<'a> {
             Shader::SolidColor(_) => true,
             Shader::LinearGradient(ref g) => g.push_stages(rec),
             Shader::RadialGradient(ref g) => g.push_stages(rec),
+            Shader::ConicalGradient(ref g) => g.push_stages(rec),
+            Shader::SweepGradient(ref g) => g.push_stages(rec),
             Shader::Pattern(ref p) => p.push_stages(rec).is_some(),
         }
     }
@@ -98,6 +148,16 @@ impl<'a> Shader<'a> {
                     g.base.transform = ts;
                 }
             }
+            Shader::ConicalGradient(g) => {
+                if let Some(ts) = g.base.transform.post_concat(ts) {
+                    g.base.transform = ts;
+                }
+            }
+            Shader::SweepGradient(g) => {
+                if let Some(ts) = g.base.transform.post_concat(ts) {
+                    g.base.transform = ts;
+                }
+            }
             Shader::Pattern(p) => {
                 if let Some(ts) = p.transform.post_concat(ts) {
                     p.transform = ts;
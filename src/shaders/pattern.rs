@@ -0,0 +1,57 @@
+// Copyright 2006 The Android Open Source Project
+// Copyright 2020 Evgeniy Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use crate::{PixmapRef, Transform};
+
+use super::{SpreadMode, StageRec};
+
+/// Controls how a pattern shader is sampled when it is scaled.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FilterQuality {
+    /// Nearest-neighbour sampling.
+    Nearest,
+    /// Bilinear sampling.
+    Bilinear,
+    /// Bicubic sampling.
+    Bicubic,
+}
+
+/// A pattern shader, backed by a pixmap.
+#[derive(Clone, Debug)]
+pub struct Pattern<'a> {
+    pub(crate) pixmap: PixmapRef<'a>,
+    pub(crate) spread_mode: SpreadMode,
+    pub(crate) quality: FilterQuality,
+    pub(crate) opacity: f32,
+    pub(crate) transform: Transform,
+}
+
+impl<'a> Pattern<'a> {
+    /// Creates a new pattern shader from a pixmap.
+    pub fn new(
+        pixmap: PixmapRef<'a>,
+        spread_mode: SpreadMode,
+        quality: FilterQuality,
+        opacity: f32,
+        transform: Transform,
+    ) -> Self {
+        Pattern {
+            pixmap,
+            spread_mode,
+            quality,
+            opacity: opacity.max(0.0).min(1.0),
+            transform,
+        }
+    }
+
+    pub(crate) fn push_stages(&self, rec: StageRec) -> Option<()> {
+        let inv = self.transform.invert()?;
+        rec.pipeline.push_transform(inv);
+        let handle = rec.ctx_storage.push(self.clone());
+        rec.pipeline.push_pattern(handle);
+        Some(())
+    }
+}
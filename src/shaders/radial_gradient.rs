@@ -0,0 +1,145 @@
+// Copyright 2006 The Android Open Source Project
+// Copyright 2020 Evgeniy Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use crate::{Color, Point, Transform};
+
+use super::gradient::{self, Gradient, GradientError, GradientStop, Interpolation, Ramp};
+use super::{SpreadMode, StageRec};
+
+/// A radial gradient shader.
+#[derive(Clone, Debug)]
+pub struct RadialGradient {
+    pub(crate) base: Gradient,
+    pub(crate) center: Point,
+    pub(crate) radius: f32,
+}
+
+impl RadialGradient {
+    /// Creates a new radial gradient.
+    ///
+    /// Returns an error when `center` or `radius` is not finite, when `radius` is not
+    /// positive, or when `stops` is malformed. A single stop is expanded into a `[0, 1]` ramp
+    /// of that one color rather than being rejected.
+    pub fn new(
+        center: Point,
+        radius: f32,
+        stops: Vec<GradientStop>,
+        mode: SpreadMode,
+        transform: Transform,
+        interpolation: Interpolation,
+    ) -> Result<Self, GradientError> {
+        gradient::validate_point(center)?;
+        gradient::validate_radius(radius)?;
+
+        if radius == 0.0 {
+            return Err(GradientError::DegenerateGeometry);
+        }
+
+        let stops = gradient::expand_single_stop(stops);
+        gradient::validate_stops(&stops)?;
+
+        Ok(RadialGradient {
+            base: Gradient {
+                stops,
+                mode,
+                transform,
+                interpolation,
+            },
+            center,
+            radius,
+        })
+    }
+
+    /// Returns the gradient's center point.
+    #[inline]
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    /// Returns the gradient's radius.
+    #[inline]
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Returns the gradient's color stops.
+    #[inline]
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.base.stops
+    }
+
+    /// Returns the gradient's spread mode.
+    #[inline]
+    pub fn mode(&self) -> SpreadMode {
+        self.base.mode
+    }
+
+    #[inline]
+    pub(crate) fn is_opaque(&self) -> bool {
+        self.base.is_opaque()
+    }
+
+    pub(crate) fn push_stages(&self, rec: StageRec) -> bool {
+        let inv = match self.base.transform.invert() {
+            Some(inv) => inv,
+            None => return false,
+        };
+
+        let ctx = Ctx {
+            ramp: self.base.ramp(),
+            center: self.center,
+            radius: self.radius,
+        };
+
+        rec.pipeline.push_transform(inv);
+        let handle = rec.ctx_storage.push(ctx);
+        rec.pipeline.push_radial_gradient(handle);
+
+        true
+    }
+}
+
+/// The precomputed per-pixel state for a radial gradient, handed to the raster pipeline.
+struct Ctx {
+    ramp: Ramp,
+    center: Point,
+    radius: f32,
+}
+
+impl Ctx {
+    fn eval(&self, p: Point) -> Color {
+        let dx = p.x - self.center.x;
+        let dy = p.y - self.center.y;
+        let t = (dx * dx + dy * dy).sqrt() / self.radius;
+
+        self.ramp.color_at(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn stop(position: f32) -> GradientStop {
+        GradientStop::new(position, Color::BLACK)
+    }
+
+    // See linear_gradient.rs's test module for why generic validation isn't re-tested here.
+    #[test]
+    fn rejects_zero_radius() {
+        let err = RadialGradient::new(
+            Point::from_xy(0.0, 0.0),
+            0.0,
+            vec![stop(0.0), stop(1.0)],
+            SpreadMode::Pad,
+            Transform::identity(),
+            Interpolation::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err, GradientError::DegenerateGeometry);
+    }
+}
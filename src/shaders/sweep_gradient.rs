@@ -0,0 +1,198 @@
+// Copyright 2006 The Android Open Source Project
+// Copyright 2020 Evgeniy Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+use core::f32::consts::PI;
+
+use crate::{Color, Point, Transform};
+
+use super::gradient::{self, Gradient, GradientError, GradientStop, Interpolation, Ramp};
+use super::{SpreadMode, StageRec};
+
+/// A sweep (angular/conic) gradient shader.
+///
+/// Sweeps its stops around `center`, starting at `start_angle` (in radians), to support CSS
+/// `conic-gradient()` and SVG-style angular fills.
+#[derive(Clone, Debug)]
+pub struct SweepGradient {
+    pub(crate) base: Gradient,
+    pub(crate) center: Point,
+    pub(crate) start_angle: f32,
+}
+
+impl SweepGradient {
+    /// Creates a new sweep gradient.
+    ///
+    /// Returns an error when `center` or `start_angle` is not finite, or when `stops` is
+    /// malformed. A single stop is expanded into a `[0, 1]` ramp of that one color rather
+    /// than being rejected.
+    pub fn new(
+        center: Point,
+        start_angle: f32,
+        stops: Vec<GradientStop>,
+        mode: SpreadMode,
+        transform: Transform,
+    ) -> Result<Self, GradientError> {
+        gradient::validate_point(center)?;
+
+        if !start_angle.is_finite() {
+            return Err(GradientError::NonFinite);
+        }
+
+        let stops = gradient::expand_single_stop(stops);
+        gradient::validate_stops(&stops)?;
+
+        Ok(SweepGradient {
+            base: Gradient {
+                stops,
+                mode,
+                transform,
+                interpolation: Interpolation::default(),
+            },
+            center,
+            start_angle,
+        })
+    }
+
+    /// Returns the gradient's center point.
+    #[inline]
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    /// Returns the angle, in radians, at which the first stop is placed.
+    #[inline]
+    pub fn start_angle(&self) -> f32 {
+        self.start_angle
+    }
+
+    /// Returns the gradient's color stops.
+    #[inline]
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.base.stops
+    }
+
+    /// Returns the gradient's spread mode.
+    #[inline]
+    pub fn mode(&self) -> SpreadMode {
+        self.base.mode
+    }
+
+    #[inline]
+    pub(crate) fn is_opaque(&self) -> bool {
+        // A sweep always maps every pixel into `[0, 1)` via `atan2`, so there's no "outside
+        // the shape" region left uncovered under any `SpreadMode` — opacity depends only on
+        // the stops, matching `LinearGradient`/`RadialGradient`.
+        self.base.is_opaque()
+    }
+
+    pub(crate) fn push_stages(&self, rec: StageRec) -> bool {
+        let inv = match self.base.transform.invert() {
+            Some(inv) => inv,
+            None => return false,
+        };
+
+        let ctx = Ctx {
+            ramp: self.base.ramp(),
+            center: self.center,
+            start_angle: self.start_angle,
+        };
+
+        rec.pipeline.push_transform(inv);
+        let handle = rec.ctx_storage.push(ctx);
+        rec.pipeline.push_sweep_gradient(handle);
+
+        true
+    }
+}
+
+/// The precomputed per-pixel state for a sweep gradient, handed to the raster pipeline.
+struct Ctx {
+    ramp: Ramp,
+    center: Point,
+    start_angle: f32,
+}
+
+impl Ctx {
+    fn eval(&self, p: Point) -> Color {
+        let dx = p.x - self.center.x;
+        let dy = p.y - self.center.y;
+
+        let theta = dy.atan2(dx) - self.start_angle;
+        let t = (theta / (2.0 * PI)).rem_euclid(1.0);
+
+        self.ramp.color_at(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn stop(position: f32) -> GradientStop {
+        GradientStop::new(position, Color::BLACK)
+    }
+
+    // See linear_gradient.rs's test module for why generic validation isn't re-tested here;
+    // `start_angle` gets its own finiteness check since it isn't a `Point` or stop list.
+    #[test]
+    fn rejects_non_finite_start_angle() {
+        let err = SweepGradient::new(
+            Point::from_xy(0.0, 0.0),
+            f32::NAN,
+            vec![stop(0.0), stop(1.0)],
+            SpreadMode::Pad,
+            Transform::identity(),
+        )
+        .unwrap_err();
+        assert_eq!(err, GradientError::NonFinite);
+    }
+
+    fn ctx(start_angle: f32) -> Ctx {
+        Ctx {
+            ramp: Gradient {
+                stops: vec![
+                    GradientStop::new(0.0, Color::BLACK),
+                    GradientStop::new(1.0, Color::WHITE),
+                ],
+                mode: SpreadMode::Pad,
+                transform: Transform::identity(),
+                interpolation: Interpolation::default(),
+            }
+            .ramp(),
+            center: Point::from_xy(0.0, 0.0),
+            start_angle,
+        }
+    }
+
+    fn red_at(c: &Ctx, theta: f32) -> f32 {
+        let p = Point::from_xy(theta.cos(), theta.sin());
+        c.eval(p).red()
+    }
+
+    #[test]
+    fn theta_zero_resolves_to_the_first_stop() {
+        // A point sitting exactly on `start_angle` maps to `t == 0`, the first (black) stop.
+        assert_eq!(red_at(&ctx(0.0), 0.0), 0.0);
+    }
+
+    #[test]
+    fn half_turn_lands_halfway_through_the_ramp() {
+        assert!((red_at(&ctx(0.0), PI) - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn quarter_turn_lands_a_quarter_through_the_ramp() {
+        assert!((red_at(&ctx(0.0), PI / 2.0) - 0.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn a_full_turn_past_start_angle_wraps_back_to_the_first_stop() {
+        // Offsetting `start_angle` by a full turn is the same physical sweep, so sampling the
+        // same point must produce the same color.
+        assert_eq!(red_at(&ctx(0.0), 0.0), red_at(&ctx(2.0 * PI), 0.0));
+    }
+}